@@ -0,0 +1,39 @@
+use std::hint::black_box;
+use std::str::FromStr;
+
+use bitcoin::secp256k1::{Secp256k1, SecretKey};
+use bitcoin::util::bip32::{ChildNumber, ExtendedPrivKey};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const ROOT: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaL\
+                    LHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+const ITERATIONS: u32 = 10_000;
+
+fn secret_key(c: &mut Criterion) {
+    let root = ExtendedPrivKey::from_str(ROOT).unwrap();
+    let secp = Secp256k1::new();
+    let path = |index| [ChildNumber::Hardened{ index: 2 }, ChildNumber::Hardened{ index }];
+
+    let mut group = c.benchmark_group("secret key, 10k indexes");
+    group.sample_size(10);
+    group.bench_function("derive + from_slice", |b| b.iter(|| {
+        for index in 0..ITERATIONS {
+            let data = bip85::derive(&secp, &root, &path(index)).unwrap();
+            black_box(SecretKey::from_slice(&data[0..32]).unwrap());
+        }
+    }));
+    group.bench_function("derive_secret_key", |b| b.iter(|| {
+        for index in 0..ITERATIONS {
+            black_box(bip85::derive_secret_key(&secp, &root, &path(index)).unwrap());
+        }
+    }));
+    group.bench_function("to_wif", |b| b.iter(|| {
+        for index in 0..ITERATIONS {
+            black_box(bip85::to_wif(&secp, &root, index).unwrap());
+        }
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, secret_key);
+criterion_main!(benches);
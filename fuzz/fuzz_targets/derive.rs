@@ -0,0 +1,92 @@
+// Feeds arbitrary root keys, paths, lengths and indexes through the public API,
+// every call should return an error instead of panicking.
+//
+// Input: 78 bytes of serialized xprv, 1 byte of the function, two 4-byte little-endian
+// numbers and the rest is used as the path (4-byte components) or a string.
+//
+//     cargo +nightly fuzz run derive fuzz/corpus/derive
+
+#![no_main]
+
+use std::num::NonZeroU64;
+use std::str::FromStr;
+
+use bip85::bitcoin::util::bip32::{ChildNumber, DerivationPath, ExtendedPrivKey};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 78 + 9 {
+        return;
+    }
+    let mut raw = [0u8; 78];
+    raw.copy_from_slice(&data[..78]);
+    let secp = bip85::global_secp();
+    let function = data[78];
+    let a = u32::from_le_bytes([data[79], data[80], data[81], data[82]]);
+    let b = u32::from_le_bytes([data[83], data[84], data[85], data[86]]);
+    let rest = &data[87..];
+    // keep valid lengths small, large ones allocate gigabytes
+    let length = if a & 0x8000_0000 != 0 { a } else { a & 0xfff };
+    let path: Vec<ChildNumber> = rest.chunks_exact(4)
+        .map(|c| ChildNumber::from(u32::from_le_bytes([c[0], c[1], c[2], c[3]])))
+        .collect();
+    let text = String::from_utf8_lossy(rest);
+
+    // malformed keys can only fail here, the library itself rejects them
+    let _ = bip85::derive_from_serialized(secp, &raw, &path);
+    let root = match ExtendedPrivKey::decode(&raw) {
+        Ok(root) => root,
+        Err(_) => return,
+    };
+
+    match function % 24 {
+        0 => { let _ = bip85::derive(secp, &root, &path); }
+        1 => { let _ = bip85::derive_unchecked(secp, &root, &path); }
+        2 => { let _ = bip85::to_wif(secp, &root, b); }
+        3 => { let _ = bip85::to_xprv(secp, &root, b); }
+        4 => { let _ = bip85::to_hex(secp, &root, a, b); }
+        5 => { let _ = bip85::to_hex_unclamped(secp, &root, length, b); }
+        6 => { let _ = bip85::to_base64_password(secp, &root, a, b); }
+        7 => { let _ = bip85::to_base85_password(secp, &root, a, b); }
+        8 => { let _ = bip85::to_dice(secp, &root, a as u16, b & 0xfff, a >> 16); }
+        9 => { let _ = bip85::to_mnemonic(secp, &root, a, b); }
+        10 => { let _ = bip85::to_password_in_alphabet(secp, &root, a, b, &text); }
+        11 => { let _ = bip85::Bip85Path::from_str(&text); }
+        12 => { let _ = bip85::identify(&DerivationPath::from(path)); }
+        13 => { let _ = bip85::derive_app_path(secp, &root, &[a, b]); }
+        14 => { let _ = bip85::to_recovery_code(secp, &root, a as usize & 0xff, b as usize & 0xff, 0); }
+        15 => {
+            let mut seed = [0u8; 64];
+            seed.copy_from_slice(&data[..64]);
+            if let Some(bound) = NonZeroU64::new((a as u64) << 32 | b as u64) {
+                let _ = bip85::sample_uniform(&mut bip85::Drng::new(&seed), bound);
+            }
+        }
+        16 => { let _ = bip85::to_pin(secp, &root, a, b); }
+        17 => {
+            let policy = bip85::PasswordPolicy {
+                uppercase: b & 1 != 0,
+                lowercase: b & 2 != 0,
+                digit: b & 4 != 0,
+                symbol: b & 8 != 0,
+            };
+            let encoding = if b & 16 != 0 {
+                bip85::PasswordEncoding::Base85
+            } else {
+                bip85::PasswordEncoding::Base64
+            };
+            let _ = bip85::to_password_policy(secp, &root, encoding, a, b >> 5, policy);
+        }
+        18 => { let _ = bip85::to_hmac_key(secp, &root, length as usize, b); }
+        19 => { let _ = bip85::to_keyed(secp, &root, b, &text, length as usize); }
+        20 => { let _ = bip85::to_slip39_shares(secp, &root, a as u8, (a >> 8) as u8, b); }
+        21 => { let _ = bip85::to_shamir_shares(secp, &root, b, a as u8, (a >> 8) as u8); }
+        22 => {
+            let shares: Vec<Vec<u8>> = rest.chunks((b as usize & 0x3f) + 1)
+                .map(|share| share.to_vec())
+                .collect();
+            let _ = bip85::combine_shamir(&shares);
+        }
+        _ => { let _ = bip85::quick::wif(&text, b); }
+    }
+});
@@ -0,0 +1,152 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! BLS12-381 secret keys from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use bitcoin::hashes::{hmac, sha256, Hash, HashEngine};
+
+use super::{app_index, derive_app_path, Error};
+
+/// Order of the BLS12-381 group, big-endian
+const CURVE_ORDER: [u8; 32] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48,
+    0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe,
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+/// Salt of HKDF_mod_r, hashed before every attempt
+const SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+/// Length of HKDF output, `ceil((3 * ceil(log2(r))) / 16)`
+const OKM_LENGTH: usize = 48;
+
+/// Derive BLS12-381 secret key from the root key
+///
+/// The key is the master secret key of [EIP-2333](https://eips.ethereum.org/EIPS/eip-2333)
+/// with the 64 bytes of the derived entropy as the seed. This is not a standard application,
+/// derivation path is `m/83696968'/667683'/{index}'`, 667683 is `BLS`, so the seed is never
+/// the same as `to_hw_seed`. The seed is expanded with HKDF-SHA256 to 48 bytes
+/// which are reduced modulo the curve order, so the key is a valid non-zero scalar.
+/// Validator keys can be derived from it with EIP-2334 paths by any EIP-2333 tool.
+///
+/// Returns big-endian 32-byte key, `index` can be any number lower than `0x80000000`
+pub fn to_bls_secret<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<[u8; 32], Error> {
+    let seed = derive_app_path(secp, root, &[app_index::BLS, index])?;
+    Ok(hkdf_mod_r(&seed))
+}
+
+/// `HKDF_mod_r` of EIP-2333 with empty key info
+fn hkdf_mod_r(ikm: &[u8]) -> [u8; 32] {
+    let mut salt = sha256::Hash::hash(SALT);
+    loop {
+        // extract
+        let mut engine = hmac::HmacEngine::<sha256::Hash>::new(&salt[..]);
+        engine.input(ikm);
+        engine.input(&[0]);
+        let prk = hmac::Hmac::from_engine(engine);
+        // expand, info is I2OSP(L, 2)
+        let mut okm = Vec::with_capacity(OKM_LENGTH + 32);
+        let mut block: Vec<u8> = Vec::new();
+        for counter in 1..=OKM_LENGTH.div_ceil(32) as u8 {
+            let mut engine = hmac::HmacEngine::<sha256::Hash>::new(&prk[..]);
+            engine.input(&block);
+            engine.input(&(OKM_LENGTH as u16).to_be_bytes());
+            engine.input(&[counter]);
+            block = hmac::Hmac::from_engine(engine)[..].to_vec();
+            okm.extend_from_slice(&block);
+        }
+        let key = reduce(&okm[..OKM_LENGTH]);
+        if key.iter().any(|b| *b != 0) {
+            return key;
+        }
+        salt = sha256::Hash::hash(&salt[..]);
+    }
+}
+
+/// Big-endian number modulo the curve order
+fn reduce(number: &[u8]) -> [u8; 32] {
+    let mut rem = [0u8; 32];
+    for byte in number {
+        for bit in (0..8).rev() {
+            // rem = 2 * rem + bit, fits 32 bytes as rem is lower than the order < 2^255
+            let mut carry = (byte >> bit) & 1;
+            for b in rem.iter_mut().rev() {
+                let next = *b >> 7;
+                *b = *b << 1 | carry;
+                carry = next;
+            }
+            if rem >= CURVE_ORDER {
+                let mut borrow = 0;
+                for (b, r) in rem.iter_mut().zip(CURVE_ORDER.iter()).rev() {
+                    let (diff, under) = b.overflowing_sub(*r);
+                    let (diff, under2) = diff.overflowing_sub(borrow);
+                    *b = diff;
+                    borrow = (under || under2) as u8;
+                }
+            }
+        }
+    }
+    rem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_hkdf_mod_r() {
+        // EIP-2333 test case 0
+        let seed = [
+            0xc5, 0x52, 0x57, 0xc3, 0x60, 0xc0, 0x7c, 0x72, 0x02, 0x9a, 0xeb, 0xc1, 0xb5, 0x3c,
+            0x05, 0xed, 0x03, 0x62, 0xad, 0xa3, 0x8e, 0xad, 0x3e, 0x3e, 0x9e, 0xfa, 0x37, 0x08,
+            0xe5, 0x34, 0x95, 0x53, 0x1f, 0x09, 0xa6, 0x98, 0x75, 0x99, 0xd1, 0x82, 0x64, 0xc1,
+            0xe1, 0xc9, 0x2f, 0x2c, 0xf1, 0x41, 0x63, 0x0c, 0x7a, 0x3c, 0x4a, 0xb7, 0xc8, 0x1b,
+            0x2f, 0x00, 0x16, 0x98, 0xe7, 0x46, 0x3b, 0x04,
+        ];
+        // master SK 6083874454709270928345386274498605044986640685124978867557563392430687146096
+        let expected = [
+            0x0d, 0x73, 0x59, 0xd5, 0x79, 0x63, 0xab, 0x8f, 0xbb, 0xde, 0x18, 0x52, 0xdc, 0xf5,
+            0x53, 0xfe, 0xdb, 0xc3, 0x1f, 0x46, 0x4d, 0x80, 0xee, 0x7d, 0x40, 0xae, 0x68, 0x31,
+            0x22, 0xb4, 0x50, 0x70,
+        ];
+        assert_eq!(hkdf_mod_r(&seed), expected);
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(reduce(&CURVE_ORDER), [0u8; 32]);
+        let mut order_plus_one = CURVE_ORDER;
+        order_plus_one[31] += 1;
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(reduce(&order_plus_one), one);
+        let mut shifted = CURVE_ORDER.to_vec();
+        shifted.extend_from_slice(&[0u8; 16]);
+        assert_eq!(reduce(&shifted), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_bls() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let key = to_bls_secret(&secp, &root, 0).unwrap();
+        assert_eq!(key[..4], [0x06, 0xfb, 0xea, 0x65]);
+        let seed = derive_app_path(&secp, &root, &[app_index::BLS, 0]).unwrap();
+        assert_eq!(key, hkdf_mod_r(&seed));
+        assert!(key < CURVE_ORDER);
+        assert_ne!(key, to_bls_secret(&secp, &root, 1).unwrap());
+
+        let index = 0x80000000;
+        assert_eq!(to_bls_secret(&secp, &root, index), Err(Error::InvalidIndex(index)));
+    }
+}
@@ -0,0 +1,102 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Cardano root keys from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use bitcoin::hashes::{hmac, sha512, Hash, HashEngine};
+use bitcoin::hashes::hex::ToHex;
+
+use super::{to_mnemonic_entropy, Error};
+
+/// Number of PBKDF2 iterations of Icarus master key generation
+const ITERATIONS: u32 = 4096;
+/// Length of the root key: 64 bytes of extended Ed25519 key and 32 bytes of chain code
+const ROOT_LENGTH: usize = 96;
+
+/// Derive Cardano root key from the root key
+///
+/// Cardano wallets use Ed25519-BIP32 keys, the root key is generated with Icarus scheme of
+/// [CIP-3](https://cips.cardano.org/cips/cip3/) from the entropy of the bip39 mnemonic:
+/// `PBKDF2-HMAC-SHA512(password = "", salt = entropy, 4096 iterations, 96 bytes)`, then
+/// the lowest 3 bits of the first byte are cleared, the highest 3 bits of the 32nd byte
+/// are set to `010`. No passphrase is used.
+///
+/// The entropy is the entropy of 24-word english mnemonic, i.e. `to_mnemonic_entropy(secp,
+/// root, 24, index)` with derivation path `m/83696968'/39'/0'/24'/{index}'`, so restoring
+/// `to_mnemonic(secp, root, 24, index)` in an Icarus-compatible wallet gives the same root key.
+///
+/// Returns 96-byte root key as hex: extended private key (kL, kR) followed by the
+/// chain code. `index` can be any number lower than `0x80000000`
+pub fn to_cardano_root<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<String, Error> {
+    let entropy = to_mnemonic_entropy(secp, root, 24, index)?;
+    Ok(icarus(&entropy).to_hex())
+}
+
+/// Icarus master key generation
+fn icarus(entropy: &[u8]) -> [u8; ROOT_LENGTH] {
+    let mut key = [0u8; ROOT_LENGTH];
+    for (i, chunk) in key.chunks_mut(64).enumerate() {
+        // PBKDF2 block i + 1 with empty password
+        let mut engine = hmac::HmacEngine::<sha512::Hash>::new(b"");
+        engine.input(entropy);
+        engine.input(&(i as u32 + 1).to_be_bytes());
+        let mut u = hmac::Hmac::from_engine(engine).into_inner();
+        let mut block = u;
+        for _ in 1..ITERATIONS {
+            let mut engine = hmac::HmacEngine::<sha512::Hash>::new(b"");
+            engine.input(&u);
+            u = hmac::Hmac::from_engine(engine).into_inner();
+            block.iter_mut().zip(u.iter()).for_each(|(b, u)| *b ^= u);
+        }
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    key[0] &= 0xf8;
+    key[31] &= 0x1f;
+    key[31] |= 0x40;
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_icarus() {
+        // CIP-3 test vector, entropy of "eight country switch draw meat scout mystery blade
+        // tip drift useless good keep usage title"
+        let entropy = [
+            0x46, 0xe6, 0x23, 0x70, 0xa1, 0x38, 0xa1, 0x82, 0xa4, 0x98,
+            0xb8, 0xe2, 0x88, 0x5b, 0xc0, 0x32, 0x37, 0x9d, 0xdf, 0x38,
+        ];
+        assert_eq!(icarus(&entropy).to_hex(),
+            "c065afd2832cd8b087c4d9ab7011f481ee1e0721e78ea5dd609f3ab3f156d245\
+             d176bd8fd4ec60b4731c3918a2a72a0226c0cd119ec35b47e4d55884667f552a\
+             23f7fdcd4a10c6cd2c7393ac61d877873e248f417634aa3d812af327ffe9d620"
+        );
+    }
+
+    #[test]
+    fn test_cardano() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let key = to_cardano_root(&secp, &root, 0).unwrap();
+        assert_eq!(key.len(), ROOT_LENGTH * 2);
+        let entropy = to_mnemonic_entropy(&secp, &root, 24, 0).unwrap();
+        assert_eq!(key, icarus(&entropy).to_hex());
+        assert_ne!(key, to_cardano_root(&secp, &root, 1).unwrap());
+
+        let index = 0x80000000;
+        assert_eq!(to_cardano_root(&secp, &root, index), Err(Error::InvalidIndex(index)));
+    }
+}
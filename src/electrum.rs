@@ -0,0 +1,173 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Electrum seeds from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use bitcoin::hashes::{hmac, sha512, Hash, HashEngine};
+use bip39::Language;
+
+use super::{app_index, derive_app_path, Error};
+
+/// Number of bits encoded by a single word
+const BITS_PER_WORD: usize = 11;
+/// Number of words in the seed before grinding
+const WORD_COUNT: usize = 12;
+
+/// Type of the Electrum seed, defines the version prefix of the seed
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ElectrumSeedType {
+    /// Standard wallet (p2pkh), version prefix `01`
+    Standard,
+    /// Segwit wallet (p2wpkh), version prefix `100`
+    Segwit,
+}
+
+impl ElectrumSeedType {
+    fn prefix(self) -> &'static str {
+        match self {
+            ElectrumSeedType::Standard => "01",
+            ElectrumSeedType::Segwit => "100",
+        }
+    }
+
+    /// Index of the seed type in the derivation path, the version prefix read as decimal
+    fn path_index(self) -> u32 {
+        match self {
+            ElectrumSeedType::Standard => 1,
+            ElectrumSeedType::Segwit => 100,
+        }
+    }
+}
+
+/// Derive Electrum seed of the given type from the root key
+///
+/// Electrum seeds are not bip39 mnemonics: they use the same English wordlist,
+/// but instead of a checksum the version of the seed is encoded in the prefix of
+/// `HMAC-SHA512("Seed version", seed)`. Electrum wallet refuses bip39 mnemonics
+/// unless explicitly asked, so use this function to get a seed for Electrum.
+///
+/// This is not a standard application, derivation path is
+/// `m/83696968'/69766967'/{seed_type}'/{index}'`, 69766967 is `ELEC`, `seed_type` is 1 for
+/// standard seeds and 100 for segwit seeds, so seeds of different types are independent.
+/// The first 17 bytes of the derived entropy are interpreted as a big-endian number and
+/// truncated to 132 bits, if the number is lower than `2^121` it's ORed with `2^121` so
+/// the seed has 12 words.
+/// Then, same as Electrum, the number is incremented until its encoding has
+/// the version prefix of `seed_type`.
+///
+/// Electrum also skips seeds that look like seeds of the old Electrum versions,
+/// this check is not performed as odds of all 12 words being in the old wordlist
+/// are negligible.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_electrum_seed<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        seed_type: ElectrumSeedType,
+        index: u32,
+    ) -> Result<String, Error> {
+    let path = [app_index::ELECTRUM, seed_type.path_index(), index];
+    let data = &derive_app_path(secp, root, &path)?[0..17];
+    // little-endian base 2048 digits of the number, same order as words in the seed
+    let mut digits: Vec<u16> = (0..WORD_COUNT).map(|i| {
+        (0..BITS_PER_WORD).fold(0u16, |digit, bit| {
+            let pos = i * BITS_PER_WORD + bit;
+            let byte = data[data.len() - 1 - pos / 8];
+            digit | ((((byte >> (pos % 8)) & 1) as u16) << bit)
+        })
+    }).collect();
+    if digits[WORD_COUNT - 1] == 0 {
+        digits[WORD_COUNT - 1] = 1;
+    }
+    let wordlist = Language::English.word_list();
+    loop {
+        increment(&mut digits);
+        let seed = digits.iter()
+            .map(|digit| wordlist[*digit as usize])
+            .collect::<Vec<_>>()
+            .join(" ");
+        if has_prefix(&seed, seed_type.prefix()) {
+            return Ok(seed);
+        }
+    }
+}
+
+/// Add one to the little-endian base 2048 number
+fn increment(digits: &mut Vec<u16>) {
+    for digit in digits.iter_mut() {
+        *digit += 1;
+        if *digit < 1 << BITS_PER_WORD {
+            return;
+        }
+        *digit = 0;
+    }
+    digits.push(1);
+}
+
+/// Check version prefix of the seed
+fn has_prefix(seed: &str, prefix: &str) -> bool {
+    let mut h = hmac::HmacEngine::<sha512::Hash>::new(b"Seed version");
+    h.input(seed.as_bytes());
+    let version = hmac::Hmac::from_engine(h).into_inner();
+    let hex: String = version[..2].iter().map(|b| format!("{:02x}", b)).collect();
+    hex.starts_with(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::util::bip32::ExtendedPrivKey;
+
+    #[test]
+    fn test_prefix() {
+        // segwit seed from Electrum test suite
+        let seed = "wild father tree among universe such mobile favorite target dynamic credit identify";
+        assert!(has_prefix(seed, ElectrumSeedType::Segwit.prefix()));
+        assert!(!has_prefix(seed, ElectrumSeedType::Standard.prefix()));
+    }
+
+    #[test]
+    fn test_increment() {
+        let mut digits = vec![2047, 5];
+        increment(&mut digits);
+        assert_eq!(digits, vec![0, 6]);
+        let mut digits = vec![2047, 2047];
+        increment(&mut digits);
+        assert_eq!(digits, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn test_electrum() {
+        let root = ExtendedPrivKey::from_str(
+            "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaL\
+             LHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb"
+        ).unwrap();
+        let secp = Secp256k1::new();
+
+        let segwit = to_electrum_seed(&secp, &root, ElectrumSeedType::Segwit, 0).unwrap();
+        assert_eq!(segwit,
+            "domain follow artwork ignore staff critic hair brush easily cargo shiver fee"
+        );
+        assert!(has_prefix(&segwit, "100"));
+
+        let derived = to_electrum_seed(&secp, &root, ElectrumSeedType::Standard, 0).unwrap();
+        assert_eq!(derived,
+            "banana rather hamster coyote vendor educate pill helmet denial giraffe poem ritual"
+        );
+        assert!(has_prefix(&derived, "01"));
+        // not a valid bip39 mnemonic
+        assert!(bip39::Mnemonic::parse(&derived).is_err());
+        // seed types don't share words
+        assert!(derived.split(' ').zip(segwit.split(' ')).all(|(a, b)| a != b));
+
+        let derived = to_electrum_seed(&secp, &root, ElectrumSeedType::Segwit, 1).unwrap();
+        assert_eq!(derived.split(' ').count(), 12);
+        assert!(has_prefix(&derived, "100"));
+    }
+}
@@ -0,0 +1,108 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Ethereum keys and addresses from the derived entropy
+
+use bitcoin::secp256k1::{self, PublicKey, Secp256k1, SecretKey};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use sha3::{Digest, Keccak256};
+
+use super::{app_index, derive_app_path, secret_key_from_entropy, Error};
+
+/// Derive Ethereum private key from the root key
+///
+/// **Warning:** this is not a standard application and the key is not derived with BIP-44,
+/// so no Ethereum wallet can recover it from the mnemonic of the root key. Funds sent to
+/// the address can only be recovered with this library or another implementation of the
+/// same derivation path, keep it with the backup of the root key.
+///
+/// Derivation path is `m/83696968'/698472'/{index}'`, 698472 is `ETH`. Private key is
+/// the first 32 bytes of the derived entropy. If the bytes are not a valid secp256k1 key
+/// `Error::InvalidDerivedKey` is returned.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_eth_private_key<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<[u8; 32], Error> {
+    let entropy = derive_app_path(secp, root, &[app_index::ETHEREUM, index])?;
+    secret_key_from_entropy(&entropy[..32])?;
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&entropy[..32]);
+    Ok(key)
+}
+
+/// Derive Ethereum address of `to_eth_private_key` from the root key
+///
+/// Returns `0x` prefixed address with [EIP-55](https://eips.ethereum.org/EIPS/eip-55)
+/// checksum.
+pub fn to_eth_address<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<String, Error> {
+    let key = secret_key_from_entropy(&to_eth_private_key(secp, root, index)?)?;
+    Ok(address(secp, &key))
+}
+
+/// Last 20 bytes of Keccak-256 of the uncompressed public key, with EIP-55 checksum
+fn address<C: secp256k1::Signing>(secp: &Secp256k1<C>, key: &SecretKey) -> String {
+    let public = PublicKey::from_secret_key(secp, key).serialize_uncompressed();
+    let hash = Keccak256::digest(&public[1..]);
+    let hex: String = hash[12..].iter().map(|b| format!("{:02x}", b)).collect();
+    checksum(&hex)
+}
+
+/// Uppercase hex letters where the matching nibble of Keccak-256 of the address is 8 or more
+fn checksum(hex: &str) -> String {
+    let hash = Keccak256::digest(hex.as_bytes());
+    let checksummed: String = hex.chars().enumerate().map(|(i, c)| {
+        let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+        if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+    }).collect();
+    format!("0x{}", checksummed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_address() {
+        let secp = Secp256k1::new();
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let key = SecretKey::from_slice(&one).unwrap();
+        assert_eq!(address(&secp, &key), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+        // EIP-55 test vectors
+        let addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for address in addresses.iter() {
+            assert_eq!(checksum(&address[2..].to_lowercase()), *address);
+        }
+    }
+
+    #[test]
+    fn test_eth() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let key = to_eth_private_key(&secp, &root, 0).unwrap();
+        let address = to_eth_address(&secp, &root, 0).unwrap();
+        assert_eq!(address, "0x2a8e0e6765c38E3EC0D43138806cDb7b77DB12FC");
+        assert_eq!(address, super::address(&secp, &SecretKey::from_slice(&key).unwrap()));
+
+        let index = 0x80000000;
+        assert_eq!(to_eth_address(&secp, &root, index), Err(Error::InvalidIndex(index)));
+    }
+}
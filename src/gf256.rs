@@ -0,0 +1,71 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Arithmetic in GF(256) shared by Shamir's and SLIP-39 shares
+
+/// Multiplication in GF(256) with the AES polynomial x^8 + x^4 + x^3 + x + 1
+pub(crate) fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(256), `a^254`
+pub(crate) fn inv(a: u8) -> u8 {
+    let mut result = 1;
+    for _ in 0..254 {
+        result = mul(result, a);
+    }
+    result
+}
+
+/// Lagrange interpolation of every byte of the points at `x`
+///
+/// Points are x coordinates with the values of the same length, x coordinates should
+/// be unique. Addition and subtraction are xor.
+pub(crate) fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    let mut result = vec![0u8; points[0].1.len()];
+    for (xi, yi) in points {
+        let basis = points.iter().filter(|(xj, _)| xj != xi).fold(1, |basis, (xj, _)| {
+            mul(basis, mul(x ^ xj, inv(xi ^ xj)))
+        });
+        for (byte, y) in result.iter_mut().zip(yi.iter()) {
+            *byte ^= mul(basis, *y);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field() {
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        for a in 1..=255 {
+            assert_eq!(mul(a, inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // y = 7 + 5x
+        let points: [(u8, &[u8]); 2] = [(1, &[7 ^ 5]), (2, &[7 ^ mul(5, 2)])];
+        assert_eq!(interpolate(&points, 0), vec![7]);
+        assert_eq!(interpolate(&points, 1), vec![7 ^ 5]);
+        assert_eq!(interpolate(&points, 3), vec![7 ^ mul(5, 3)]);
+    }
+}
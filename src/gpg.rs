@@ -0,0 +1,150 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! OpenPGP Ed25519 keys from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use bitcoin::hashes::{sha1, Hash};
+use bitcoin::hashes::hex::ToHex;
+use ed25519_dalek::SigningKey;
+
+use super::{app_index, derive_app_path, Error};
+
+/// OID of Ed25519 curve, 1.3.6.1.4.1.11591.15.1
+const ED25519_OID: [u8; 9] = [0x2b, 0x06, 0x01, 0x04, 0x01, 0xda, 0x47, 0x0f, 0x01];
+/// Public key algorithm EdDSA
+const ALGORITHM_EDDSA: u8 = 22;
+/// Packet tag of the secret key
+const TAG_SECRET_KEY: u8 = 5;
+
+/// Derive OpenPGP Ed25519 primary key from the root key as secret-key packet
+///
+/// This is not a standard application, derivation path is `m/83696968'/718071'/{index}'`,
+/// 718071 is `GPG`. The private key is the first 32 bytes of the derived entropy.
+///
+/// Returns version 4 secret-key packet of [RFC 4880](https://tools.ietf.org/html/rfc4880)
+/// with EdDSA key of [RFC 4880bis](https://datatracker.ietf.org/doc/html/draft-ietf-openpgp-rfc4880bis),
+/// the secret is not encrypted. Fingerprint of OpenPGP key includes its creation time,
+/// so `creation_time` (unix timestamp) has to be the same to get the same key.
+/// The packet has no user ID and signatures, add them with OpenPGP tools.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_gpg_ed25519<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        creation_time: u32,
+        index: u32,
+    ) -> Result<Vec<u8>, Error> {
+    let key = signing_key(secp, root, index)?;
+    let mut body = public_key_body(&key, creation_time);
+    // string-to-key usage 0: not encrypted
+    body.push(0);
+    let mpi = mpi(key.as_bytes());
+    let checksum = mpi.iter().fold(0u16, |sum, b| sum.wrapping_add(*b as u16));
+    body.extend_from_slice(&mpi);
+    body.extend_from_slice(&checksum.to_be_bytes());
+
+    // new format header, body is shorter than 192 bytes so length takes one byte
+    let mut packet = vec![0xc0 | TAG_SECRET_KEY, body.len() as u8];
+    packet.extend_from_slice(&body);
+    Ok(packet)
+}
+
+/// Derive fingerprint of `to_gpg_ed25519` key from the root key
+///
+/// Returns version 4 fingerprint as uppercase hex, the way OpenPGP tools show it.
+pub fn to_gpg_ed25519_fingerprint<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        creation_time: u32,
+        index: u32,
+    ) -> Result<String, Error> {
+    let key = signing_key(secp, root, index)?;
+    Ok(fingerprint(&public_key_body(&key, creation_time)).to_hex().to_uppercase())
+}
+
+fn signing_key<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<SigningKey, Error> {
+    let entropy = derive_app_path(secp, root, &[app_index::GPG, index])?;
+    let mut secret = [0u8; 32];
+    secret.copy_from_slice(&entropy[..32]);
+    Ok(SigningKey::from_bytes(&secret))
+}
+
+/// Body of version 4 public-key packet
+fn public_key_body(key: &SigningKey, creation_time: u32) -> Vec<u8> {
+    let mut body = vec![4];
+    body.extend_from_slice(&creation_time.to_be_bytes());
+    body.push(ALGORITHM_EDDSA);
+    body.push(ED25519_OID.len() as u8);
+    body.extend_from_slice(&ED25519_OID);
+    // native point format, 0x40 prefix
+    let mut point = vec![0x40];
+    point.extend_from_slice(key.verifying_key().as_bytes());
+    body.extend_from_slice(&mpi(&point));
+    body
+}
+
+/// Multiprecision integer: bit length and big-endian number without leading zeros
+fn mpi(number: &[u8]) -> Vec<u8> {
+    let start = number.iter().position(|b| *b != 0).unwrap_or(number.len());
+    let number = &number[start..];
+    let bits = match number.first() {
+        Some(first) => number.len() * 8 - first.leading_zeros() as usize,
+        None => 0,
+    };
+    let mut mpi = (bits as u16).to_be_bytes().to_vec();
+    mpi.extend_from_slice(number);
+    mpi
+}
+
+/// Version 4 fingerprint of the public key
+fn fingerprint(public_key_body: &[u8]) -> sha1::Hash {
+    let mut data = vec![0x99];
+    data.extend_from_slice(&(public_key_body.len() as u16).to_be_bytes());
+    data.extend_from_slice(public_key_body);
+    sha1::Hash::hash(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_mpi() {
+        assert_eq!(mpi(&[0x00, 0x01]), [0x00, 0x01, 0x01]);
+        assert_eq!(mpi(&[0x01, 0xff]), [0x00, 0x09, 0x01, 0xff]);
+        assert_eq!(mpi(&[0x40, 0x00]), [0x00, 0x0f, 0x40, 0x00]);
+        assert_eq!(mpi(&[0x00]), [0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_gpg() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let packet = to_gpg_ed25519(&secp, &root, 1600000000, 0).unwrap();
+        assert_eq!(packet, to_gpg_ed25519(&secp, &root, 1600000000, 0).unwrap());
+        assert_eq!(packet[0], 0xc5);
+        assert_eq!(packet[1] as usize, packet.len() - 2);
+        let public_len = 1 + 4 + 1 + 1 + ED25519_OID.len() + 2 + 33;
+        let public_body = &packet[2..2 + public_len];
+        assert_eq!(fingerprint(public_body).to_string(), "c2d1c0cd5a9eb0106121d78a79ea2357b235dcfd");
+        assert_eq!(to_gpg_ed25519_fingerprint(&secp, &root, 1600000000, 0).unwrap(),
+                   "C2D1C0CD5A9EB0106121D78A79EA2357B235DCFD");
+
+        let other = to_gpg_ed25519(&secp, &root, 1600000001, 0).unwrap();
+        assert_ne!(fingerprint(&other[2..2 + public_len]), fingerprint(public_body));
+
+        let index = 0x80000000;
+        assert_eq!(to_gpg_ed25519(&secp, &root, 0, index), Err(Error::InvalidIndex(index)));
+    }
+}
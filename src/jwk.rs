@@ -0,0 +1,104 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Ed25519 JSON Web Keys from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use ed25519_dalek::SigningKey;
+
+use super::{app_index, base64_encode, derive_app_path, Error};
+
+/// Derive Ed25519 private key from the root key as JWK
+///
+/// The key is serialized as [RFC 7517](https://tools.ietf.org/html/rfc7517) JSON object
+/// with `kty` `OKP` and `crv` `Ed25519` as defined in
+/// [RFC 8037](https://tools.ietf.org/html/rfc8037), `d` is the private key and `x` - the public.
+///
+/// This is not a standard application, derivation path is `m/83696968'/748775'/{index}'`,
+/// 748775 is `JWK`. The private key is the first 32 bytes of the derived entropy.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_ed25519_jwk<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<String, Error> {
+    Ok(jwk(&signing_key(secp, root, index)?, true))
+}
+
+/// Derive Ed25519 public key from the root key as JWK
+///
+/// Same as `to_ed25519_jwk`, but without the private key `d`.
+pub fn to_ed25519_jwk_public<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<String, Error> {
+    Ok(jwk(&signing_key(secp, root, index)?, false))
+}
+
+fn signing_key<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<SigningKey, Error> {
+    let entropy = derive_app_path(secp, root, &[app_index::JWK, index])?;
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&entropy[..32]);
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Serialize the key as JWK, with private part if `private` is set
+fn jwk(key: &SigningKey, private: bool) -> String {
+    let x = base64url(key.verifying_key().as_bytes());
+    if private {
+        let d = base64url(key.as_bytes());
+        format!(r#"{{"kty":"OKP","crv":"Ed25519","d":"{}","x":"{}"}}"#, d, x)
+    } else {
+        format!(r#"{{"kty":"OKP","crv":"Ed25519","x":"{}"}}"#, x)
+    }
+}
+
+/// Base64 encoding with URL and filename safe alphabet without padding
+fn base64url(data: &[u8]) -> String {
+    base64_encode(data).trim_end_matches('=').replace('+', "-").replace('/', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_jwk() {
+        // RFC 8037, appendix A.1
+        let seed = [0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60,
+                    0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c, 0xc4,
+                    0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19,
+                    0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae, 0x7f, 0x60,
+        ];
+        let key = SigningKey::from_bytes(&seed);
+        assert_eq!(jwk(&key, true), "{\"kty\":\"OKP\",\"crv\":\"Ed25519\",\
+            \"d\":\"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A\",\
+            \"x\":\"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo\"}");
+        assert_eq!(jwk(&key, false), "{\"kty\":\"OKP\",\"crv\":\"Ed25519\",\
+            \"x\":\"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo\"}");
+    }
+
+    #[test]
+    fn test_ed25519_jwk() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let private = to_ed25519_jwk(&secp, &root, 0).unwrap();
+        let public = to_ed25519_jwk_public(&secp, &root, 0).unwrap();
+        assert!(private.contains("\"d\":\"_5i9-lq87xZ6vYNB9K9xmKWsfvL7iQhh3ZwEBnkyv_s\""));
+        let x = &public[public.find("\"x\"").unwrap()..];
+        assert!(private.ends_with(x));
+        assert_ne!(public, to_ed25519_jwk_public(&secp, &root, 1).unwrap());
+    }
+}
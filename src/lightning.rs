@@ -0,0 +1,64 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Lightning node keys from the derived entropy
+
+use bitcoin::secp256k1::{self, PublicKey, Secp256k1, SecretKey};
+use bitcoin::util::bip32::ExtendedPrivKey;
+
+use super::{app_index, derive_app_path, secret_key_from_entropy, Error};
+
+/// Derive static secret key of Lightning node from the root key
+///
+/// This is not a standard application, derivation path is `m/83696968'/7678'/{index}'`,
+/// 7678 is `LN`. Node key is the first 32 bytes of the derived entropy. Fails with
+/// `Error::InvalidDerivedKey` if the bytes aren't a valid secret key.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_node_key<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<SecretKey, Error> {
+    let entropy = derive_app_path(secp, root, &[app_index::LIGHTNING, index])?;
+    secret_key_from_entropy(&entropy[..32])
+}
+
+/// Derive Lightning node id from the root key
+///
+/// Node id is the compressed public key of `to_node_key`.
+pub fn to_node_id<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<PublicKey, Error> {
+    let key = to_node_key(secp, root, index)?;
+    Ok(PublicKey::from_secret_key(secp, &key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_node_id() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let key = to_node_key(&secp, &root, 0).unwrap();
+        let node_id = to_node_id(&secp, &root, 0).unwrap();
+        assert_eq!(
+            node_id.to_string(),
+            "02b8dcb961ee5ce4b1e2435cd9f4040b57bac8acc834362bc5bff11c9a3e6516c3"
+        );
+        assert_eq!(node_id, PublicKey::from_secret_key(&secp, &key));
+        assert_ne!(key, to_node_key(&secp, &root, 1).unwrap());
+
+        let index = 0x80000000;
+        assert_eq!(to_node_id(&secp, &root, index), Err(Error::InvalidIndex(index)));
+    }
+}
@@ -0,0 +1,113 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Monero seeds from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use curve25519_dalek::scalar::Scalar;
+
+use super::{app_index, derive_app_path, Error};
+
+mod english;
+
+/// Number of characters identifying the word in the checksum
+const UNIQUE_PREFIX_LENGTH: usize = 3;
+
+/// Derive 25-word Monero mnemonic from the root key
+///
+/// This is not a standard application, derivation path is `m/83696968'/887782'/{index}'`,
+/// 887782 is `XMR`. The first 32 bytes of the derived entropy are used.
+///
+/// Derived bytes are interpreted as a little-endian integer and reduced modulo the
+/// ed25519 group order (`sc_reduce32` in Monero), the result is the private spend key.
+/// The mnemonic encodes the spend key with the English wordlist: 24 words encode the key
+/// and the last one is a checksum word, same as `monero-wallet-cli` does.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_monero_seed<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<String, Error> {
+    let data = derive_app_path(secp, root, &[app_index::MONERO, index])?;
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[0..32]);
+    let spend_key = Scalar::from_bytes_mod_order(bytes);
+    Ok(encode(&spend_key.to_bytes()))
+}
+
+/// Encode 32 bytes as a Monero mnemonic
+fn encode(data: &[u8; 32]) -> String {
+    let n = english::WORDS.len() as u32;
+    let mut words = Vec::with_capacity(25);
+    for chunk in data.chunks(4) {
+        let val = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let w1 = val % n;
+        let w2 = (val / n + w1) % n;
+        let w3 = (val / n / n + w2) % n;
+        words.push(english::WORDS[w1 as usize]);
+        words.push(english::WORDS[w2 as usize]);
+        words.push(english::WORDS[w3 as usize]);
+    }
+    let prefixes: String = words.iter().map(|word| &word[..UNIQUE_PREFIX_LENGTH]).collect();
+    let checksum = crc32(prefixes.as_bytes()) as usize % words.len();
+    words.push(words[checksum]);
+    words.join(" ")
+}
+
+/// CRC-32 (IEEE) used for the checksum word
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::util::bip32::ExtendedPrivKey;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+    }
+
+    #[test]
+    fn test_monero() {
+        let root = ExtendedPrivKey::from_str(
+            "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaL\
+             LHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb"
+        ).unwrap();
+        let secp = Secp256k1::new();
+
+        // spend key dd3e2cb8e23db9242f60cce23c42d33a136586544253dc3ab2d3c99972f0ad06
+        let data = derive_app_path(&secp, &root, &[app_index::MONERO, 0]).unwrap();
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&data[0..32]);
+        let spend_key = Scalar::from_bytes_mod_order(bytes).to_bytes();
+        let derived = to_monero_seed(&secp, &root, 0).unwrap();
+        let expected = "asked shuffled licks juicy licks oxygen vitals byline afloat atom gutter \
+                        nuisance jargon speedy bygones lively acoustic eels never kickoff army dads \
+                        nasty nudged vitals";
+        assert_eq!(derived, expected);
+        assert_eq!(derived, encode(&spend_key));
+
+        let derived = to_monero_seed(&secp, &root, 1).unwrap();
+        assert_eq!(derived.split(' ').count(), 25);
+        assert_ne!(derived, expected);
+
+        let index = 0x80000000;
+        assert_eq!(to_monero_seed(&secp, &root, index), Err(Error::InvalidIndex(index)));
+    }
+}
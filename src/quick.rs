@@ -0,0 +1,87 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! One-call derivations from the xprv string for scripts
+//!
+//! Every function parses the root key, derives with the shared secp256k1 context and
+//! returns the string form of the result. Invalid root key gives `Error::ParseRoot`.
+//!
+//! ```
+//! # fn main() -> Result<(), bip85::Error> {
+//! const XPRV: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaL\
+//!                     LHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+//! let wif = bip85::quick::wif(XPRV, 0)?;
+//! assert_eq!(wif, "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::str::FromStr;
+
+use bitcoin::hashes::hex::ToHex;
+use bitcoin::util::bip32::ExtendedPrivKey;
+
+use super::{shared_secp, Error};
+
+fn parse_root(xprv: &str) -> Result<ExtendedPrivKey, Error> {
+    ExtendedPrivKey::from_str(xprv).map_err(Error::ParseRoot)
+}
+
+/// WIF private key, see `to_wif`
+pub fn wif(xprv: &str, index: u32) -> Result<String, Error> {
+    Ok(super::to_wif(shared_secp(), &parse_root(xprv)?, index)?.to_wif())
+}
+
+/// Extended private key, see `to_xprv`
+pub fn xprv(xprv: &str, index: u32) -> Result<String, Error> {
+    Ok(super::to_xprv(shared_secp(), &parse_root(xprv)?, index)?.to_string())
+}
+
+/// Lowercase hex of `length` bytes, see `to_hex`
+pub fn hex(xprv: &str, length: u32, index: u32) -> Result<String, Error> {
+    Ok(super::to_hex(shared_secp(), &parse_root(xprv)?, length, index)?.to_hex())
+}
+
+/// English mnemonic of `word_count` words, see `to_mnemonic`
+#[cfg(feature = "mnemonic")]
+pub fn mnemonic(xprv: &str, word_count: u32, index: u32) -> Result<String, Error> {
+    Ok(super::to_mnemonic(shared_secp(), &parse_root(xprv)?, word_count, index)?.to_string())
+}
+
+/// Base64 password, see `to_base64_password`
+pub fn base64_password(xprv: &str, length: u32, index: u32) -> Result<String, Error> {
+    super::to_base64_password(shared_secp(), &parse_root(xprv)?, length, index)
+}
+
+/// Base85 password, see `to_base85_password`
+pub fn base85_password(xprv: &str, length: u32, index: u32) -> Result<String, Error> {
+    super::to_base85_password(shared_secp(), &parse_root(xprv)?, length, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::vectors;
+
+    #[test]
+    fn test_quick() {
+        assert_eq!(wif(vectors::ROOT, vectors::WIF.0).unwrap(), vectors::WIF.1);
+        assert_eq!(xprv(vectors::ROOT, vectors::XPRV.0).unwrap(), vectors::XPRV.1);
+        let (length, index, expected) = vectors::HEX;
+        assert_eq!(hex(vectors::ROOT, length, index).unwrap(), expected.to_hex());
+        #[cfg(feature = "mnemonic")]
+        {
+            let (word_count, index, expected) = vectors::MNEMONIC[0];
+            assert_eq!(mnemonic(vectors::ROOT, word_count, index).unwrap(), expected);
+        }
+        let (length, index, expected) = vectors::PWD_BASE64;
+        assert_eq!(base64_password(vectors::ROOT, length, index).unwrap(), expected);
+        let (length, index, expected) = vectors::PWD_BASE85;
+        assert_eq!(base85_password(vectors::ROOT, length, index).unwrap(), expected);
+
+        assert!(matches!(wif("xprv", 0), Err(Error::ParseRoot(_))));
+        assert_eq!(hex(vectors::ROOT, 8, 0), Err(Error::InvalidLength(8)));
+    }
+}
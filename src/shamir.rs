@@ -0,0 +1,132 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Shamir's secret sharing of the derived entropy over GF(256)
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+
+use super::{app_index, derive_app_path, gf256, Drng, Error};
+
+/// Length of the secret in bytes
+const SECRET_LENGTH: usize = 32;
+
+/// Derive secret and split it into Shamir's shares
+///
+/// This is not a standard application, derivation path is
+/// `m/83696968'/838383'/{threshold}'/{share_count}'/{index}'`, 838383 is `SSS`. The derived
+/// entropy seeds BIP85-DRNG, the stream gives the 32-byte secret and then coefficients
+/// of the polynomials, so the shares are the same on every call.
+///
+/// Every share is 33 bytes: x coordinate from 1 to `share_count` followed by 32 bytes
+/// of the polynomials evaluated at x. Any `threshold` shares can be combined back to the
+/// secret with `combine_shamir`.
+///
+/// `threshold` should be between 1 and `share_count`, `share_count` can't exceed 255,
+/// `index` can be any number lower than `0x80000000`
+pub fn to_shamir_shares<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+        threshold: u8,
+        share_count: u8,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+    if threshold == 0 || threshold > share_count {
+        return Err(Error::InvalidThreshold(threshold, share_count));
+    }
+    let entropy = derive_app_path(secp, root, &[
+        app_index::SHAMIR, threshold as u32, share_count as u32, index,
+    ])?;
+    let mut drng = Drng::new(&entropy);
+    let mut secret = [0u8; SECRET_LENGTH];
+    drng.fill_bytes(&mut secret);
+
+    // polynomial of every byte: secret byte followed by `threshold - 1` coefficients
+    let polynomials: Vec<Vec<u8>> = secret.iter().map(|byte| {
+        let mut coefficients = vec![0u8; threshold as usize];
+        coefficients[0] = *byte;
+        drng.fill_bytes(&mut coefficients[1..]);
+        coefficients
+    }).collect();
+
+    Ok((1..=share_count).map(|x| {
+        let mut share = Vec::with_capacity(SECRET_LENGTH + 1);
+        share.push(x);
+        share.extend(polynomials.iter().map(|coefficients| {
+            coefficients.iter().rev().fold(0, |y, c| gf256::mul(y, x) ^ c)
+        }));
+        share
+    }).collect())
+}
+
+/// Combine Shamir's shares created by `to_shamir_shares` back into the secret
+///
+/// At least `threshold` shares are needed, with fewer shares the result is just wrong:
+/// Shamir's shares have no checksum. If there are no shares, shares have different
+/// lengths or the same x coordinate, `Error::InvalidShares` is returned.
+pub fn combine_shamir(shares: &[Vec<u8>]) -> Result<Vec<u8>, Error> {
+    let len = match shares.first() {
+        Some(share) if share.len() > 1 => share.len(),
+        _ => return Err(Error::InvalidShares),
+    };
+    for (i, share) in shares.iter().enumerate() {
+        if share.len() != len || share[0] == 0 || shares[..i].iter().any(|s| s[0] == share[0]) {
+            return Err(Error::InvalidShares);
+        }
+    }
+    let points: Vec<(u8, &[u8])> = shares.iter().map(|share| (share[0], &share[1..])).collect();
+    Ok(gf256::interpolate(&points, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    fn secret(threshold: u8, share_count: u8, index: u32) -> Vec<u8> {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let path = [app_index::SHAMIR, threshold as u32, share_count as u32, index];
+        let entropy = derive_app_path(&Secp256k1::new(), &root, &path).unwrap();
+        let mut secret = vec![0u8; SECRET_LENGTH];
+        Drng::new(&entropy).fill_bytes(&mut secret);
+        secret
+    }
+
+    #[test]
+    fn test_shamir() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+        let secret = secret(3, 5, 0);
+        assert_eq!(secret[..4], [0xbf, 0xb4, 0xe9, 0x07]);
+
+        let shares = to_shamir_shares(&secp, &root, 0, 3, 5).unwrap();
+        assert_eq!(shares, to_shamir_shares(&secp, &root, 0, 3, 5).unwrap());
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|share| share.len() == 33));
+        assert_eq!(combine_shamir(&shares[0..3]).unwrap(), secret);
+        assert_eq!(combine_shamir(&shares[2..5]).unwrap(), secret);
+        assert_eq!(combine_shamir(&shares).unwrap(), secret);
+        let shuffled = [shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert_eq!(combine_shamir(&shuffled).unwrap(), secret);
+        assert_ne!(combine_shamir(&shares[0..2]).unwrap(), secret);
+        assert_ne!(to_shamir_shares(&secp, &root, 1, 3, 5).unwrap(), shares);
+
+        let shares = to_shamir_shares(&secp, &root, 0, 1, 1).unwrap();
+        assert_eq!(shares[0][1..], self::secret(1, 1, 0)[..]);
+        let shares = to_shamir_shares(&secp, &root, 0, 255, 255).unwrap();
+        assert_eq!(combine_shamir(&shares).unwrap(), self::secret(255, 255, 0));
+
+        assert_eq!(to_shamir_shares(&secp, &root, 0, 0, 5), Err(Error::InvalidThreshold(0, 5)));
+        assert_eq!(to_shamir_shares(&secp, &root, 0, 6, 5), Err(Error::InvalidThreshold(6, 5)));
+        assert_eq!(combine_shamir(&[]), Err(Error::InvalidShares));
+        let duplicate = [shares[0].clone(), shares[0].clone()];
+        assert_eq!(combine_shamir(&duplicate), Err(Error::InvalidShares));
+        let truncated = [shares[0].clone(), shares[1][..10].to_vec()];
+        assert_eq!(combine_shamir(&truncated), Err(Error::InvalidShares));
+        let index = 0x80000000;
+        assert_eq!(to_shamir_shares(&secp, &root, index, 3, 5), Err(Error::InvalidIndex(index)));
+    }
+}
@@ -0,0 +1,212 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! SLIP-39 shares of the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use bitcoin::hashes::{hmac, sha256, Hash, HashEngine};
+use sssmc39::Share;
+
+use super::{app_index, derive_app_path, gf256, Drng, Error};
+
+/// Length of the master secret in bytes
+const MASTER_SECRET_LENGTH: usize = 16;
+/// Maximum number of shares allowed by SLIP-39
+const MAX_SHARE_COUNT: u8 = 16;
+/// Customization string of SLIP-39, salt prefix of the encryption
+const CUSTOMIZATION_STRING: &[u8] = b"shamir";
+/// PBKDF2 iterations of every of the 4 Feistel rounds for iteration exponent 0
+const BASE_ITERATIONS: u32 = 2500;
+/// Number of Feistel rounds
+const ROUND_COUNT: u8 = 4;
+/// Length of the digest of the shared secret
+const DIGEST_LENGTH: usize = 4;
+/// x coordinate of the digest share
+const DIGEST_INDEX: u8 = 254;
+/// x coordinate of the shared secret
+const SECRET_INDEX: u8 = 255;
+
+/// Derive master secret and split it into SLIP-39 mnemonic shares
+///
+/// This is not a standard application, derivation path is
+/// `m/83696968'/83767380'/{threshold}'/{share_count}'/{index}'`. The derived entropy seeds
+/// BIP85-DRNG, the stream gives the 16-byte master secret, then 2 bytes of the identifier
+/// (15 lowest bits of the big-endian number) and then all random values of SLIP-39 splitting.
+/// So the shares are the same on every call, and different thresholds or share counts give
+/// different master secrets.
+///
+/// The secret is split into `share_count` shares forming a single group, any `threshold`
+/// of them can be combined back to the master secret by any SLIP-39 implementation.
+/// No passphrase is used and iteration exponent is 0.
+///
+/// `threshold` should be between 1 and `share_count`, `share_count` can't exceed 16,
+/// `index` can be any number lower than `0x80000000`
+pub fn to_slip39_shares<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        threshold: u8,
+        share_count: u8,
+        index: u32,
+    ) -> Result<Vec<String>, Error> {
+    if threshold == 0 || threshold > share_count || share_count > MAX_SHARE_COUNT {
+        return Err(Error::InvalidThreshold(threshold, share_count));
+    }
+    let entropy = derive_app_path(secp, root, &[
+        app_index::SLIP39, threshold as u32, share_count as u32, index,
+    ])?;
+    let mut drng = Drng::new(&entropy);
+    let mut secret = [0u8; MASTER_SECRET_LENGTH];
+    drng.fill_bytes(&mut secret);
+    let mut identifier = [0u8; 2];
+    drng.fill_bytes(&mut identifier);
+    let identifier = u16::from_be_bytes(identifier) & 0x7fff;
+
+    let encrypted = encrypt(&secret, identifier);
+    let share_values = split(&mut drng, &encrypted, threshold, share_count);
+    share_values.into_iter().enumerate().map(|(member_index, share_value)| {
+        let share = Share {
+            identifier,
+            iteration_exponent: 0,
+            group_index: 0,
+            group_threshold: 1,
+            group_count: 1,
+            member_index: member_index as u8,
+            member_threshold: threshold,
+            share_value,
+            ..Share::default()
+        };
+        Ok(share.to_mnemonic()?.join(" "))
+    }).collect()
+}
+
+/// Encrypt the master secret with the Feistel cipher of SLIP-39 and empty passphrase
+fn encrypt(secret: &[u8], identifier: u16) -> Vec<u8> {
+    let half = secret.len() / 2;
+    let mut left = secret[..half].to_vec();
+    let mut right = secret[half..].to_vec();
+    let mut salt = CUSTOMIZATION_STRING.to_vec();
+    salt.extend_from_slice(&identifier.to_be_bytes());
+    for round in 0..ROUND_COUNT {
+        let mut round_salt = salt.clone();
+        round_salt.extend_from_slice(&right);
+        let key = pbkdf2(&[round], &round_salt, BASE_ITERATIONS, half);
+        let next: Vec<u8> = left.iter().zip(key.iter()).map(|(l, k)| l ^ k).collect();
+        left = right;
+        right = next;
+    }
+    right.extend_from_slice(&left);
+    right
+}
+
+/// PBKDF2-HMAC-SHA256 of up to 32 bytes
+fn pbkdf2(password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Vec<u8> {
+    let mut engine = hmac::HmacEngine::<sha256::Hash>::new(password);
+    engine.input(salt);
+    engine.input(&1u32.to_be_bytes());
+    let mut u = hmac::Hmac::from_engine(engine).into_inner();
+    let mut block = u;
+    for _ in 1..iterations {
+        let mut engine = hmac::HmacEngine::<sha256::Hash>::new(password);
+        engine.input(&u);
+        u = hmac::Hmac::from_engine(engine).into_inner();
+        block.iter_mut().zip(u.iter()).for_each(|(b, u)| *b ^= u);
+    }
+    block[..length].to_vec()
+}
+
+/// Split the secret into share values with x coordinates from 0 to `share_count - 1`
+///
+/// Same as SLIP-39: `threshold - 2` random shares, the digest share and the secret
+/// define the polynomials, other shares are interpolated.
+fn split(drng: &mut Drng, secret: &[u8], threshold: u8, share_count: u8) -> Vec<Vec<u8>> {
+    if threshold == 1 {
+        return vec![secret.to_vec(); share_count as usize];
+    }
+    let mut random_shares = Vec::with_capacity(threshold as usize - 2);
+    for _ in 0..threshold - 2 {
+        let mut value = vec![0u8; secret.len()];
+        drng.fill_bytes(&mut value);
+        random_shares.push(value);
+    }
+    let mut random_part = vec![0u8; secret.len() - DIGEST_LENGTH];
+    drng.fill_bytes(&mut random_part);
+    let mut engine = hmac::HmacEngine::<sha256::Hash>::new(&random_part);
+    engine.input(secret);
+    let mut digest = hmac::Hmac::from_engine(engine)[..DIGEST_LENGTH].to_vec();
+    digest.extend_from_slice(&random_part);
+
+    let mut points: Vec<(u8, &[u8])> = random_shares.iter().enumerate()
+        .map(|(x, value)| (x as u8, &value[..]))
+        .collect();
+    points.push((DIGEST_INDEX, &digest));
+    points.push((SECRET_INDEX, secret));
+    let mut shares = random_shares.clone();
+    shares.extend((threshold - 2..share_count).map(|x| gf256::interpolate(&points, x)));
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::util::bip32::ExtendedPrivKey;
+    use crate::vectors;
+
+    fn combine(shares: &[String]) -> Vec<u8> {
+        let mnemonics: Vec<Vec<String>> = shares.iter().map(|share| {
+            share.split(' ').map(String::from).collect()
+        }).collect();
+        sssmc39::combine_mnemonics(&mnemonics, "").unwrap()
+    }
+
+    fn master_secret(threshold: u8, share_count: u8, index: u32) -> Vec<u8> {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let path = [app_index::SLIP39, threshold as u32, share_count as u32, index];
+        let entropy = derive_app_path(&Secp256k1::new(), &root, &path).unwrap();
+        let mut secret = vec![0u8; MASTER_SECRET_LENGTH];
+        Drng::new(&entropy).fill_bytes(&mut secret);
+        secret
+    }
+
+    #[test]
+    fn test_pbkdf2() {
+        // RFC 7914 test vector of PBKDF2-HMAC-SHA256
+        let derived = pbkdf2(b"passwd", b"salt", 1, 32);
+        assert_eq!(derived[..8], [0x55, 0xac, 0x04, 0x6e, 0x56, 0xe3, 0x08, 0x9f]);
+    }
+
+    #[test]
+    fn test_slip39() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+        let secret = master_secret(3, 5, 0);
+
+        let shares = to_slip39_shares(&secp, &root, 3, 5, 0).unwrap();
+        assert_eq!(shares, to_slip39_shares(&secp, &root, 3, 5, 0).unwrap());
+        assert_eq!(shares.len(), 5);
+        for share in shares.iter() {
+            assert_eq!(share.split(' ').count(), 20);
+        }
+        assert_eq!(combine(&shares[0..3]), secret);
+        assert_eq!(combine(&shares[2..5]), secret);
+        assert_eq!(combine(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]), secret);
+        assert_ne!(to_slip39_shares(&secp, &root, 3, 5, 1).unwrap(), shares);
+
+        let shares = to_slip39_shares(&secp, &root, 2, 2, 0).unwrap();
+        assert_eq!(combine(&shares), master_secret(2, 2, 0));
+        let shares = to_slip39_shares(&secp, &root, 1, 1, 0).unwrap();
+        assert_eq!(combine(&shares), master_secret(1, 1, 0));
+        let shares = to_slip39_shares(&secp, &root, 16, 16, 0).unwrap();
+        assert_eq!(combine(&shares), master_secret(16, 16, 0));
+
+        assert_eq!(to_slip39_shares(&secp, &root, 0, 5, 0), Err(Error::InvalidThreshold(0, 5)));
+        assert_eq!(to_slip39_shares(&secp, &root, 6, 5, 0), Err(Error::InvalidThreshold(6, 5)));
+        assert_eq!(to_slip39_shares(&secp, &root, 2, 17, 0), Err(Error::InvalidThreshold(2, 17)));
+        let index = 0x80000000;
+        assert_eq!(to_slip39_shares(&secp, &root, 3, 5, index), Err(Error::InvalidIndex(index)));
+    }
+}
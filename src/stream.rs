@@ -0,0 +1,91 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Asynchronous batches of derivations
+
+use std::io;
+use std::sync::Arc;
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use bip39::Mnemonic;
+use futures::stream::{self, Stream, StreamExt};
+
+use super::{to_mnemonic, Error};
+
+/// Derive english mnemonics for every index as a stream
+///
+/// Same as calling `to_mnemonic(secp, root, word_count, index)` for every index of
+/// `indexes`, mnemonics are yielded in the order of indexes. Derivations are CPU-bound,
+/// so inside tokio runtime they run in `spawn_blocking` tasks, as many in parallel
+/// as there are CPUs. Without runtime mnemonics are derived on the polling thread.
+///
+/// `word_count` can be 12, 18 or 24, indexes can be any number lower than `0x80000000`,
+/// invalid ones give errors in the stream.
+pub fn mnemonic_stream<C, I>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        word_count: u32,
+        indexes: I,
+    ) -> impl Stream<Item = Result<Mnemonic, Error>>
+    where C: secp256k1::Signing + Send + Sync + 'static,
+          I: IntoIterator<Item = u32>,
+{
+    let secp = Arc::new(secp.clone());
+    let root = *root;
+    let parallelism = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    stream::iter(indexes).map(move |index| {
+        let secp = secp.clone();
+        async move {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    let task = handle.spawn_blocking(move || {
+                        to_mnemonic(&secp, &root, word_count, index)
+                    });
+                    match task.await {
+                        Ok(result) => result,
+                        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                        // runtime is shutting down
+                        Err(_) => Err(Error::Io(io::ErrorKind::Interrupted)),
+                    }
+                }
+                Err(_) => to_mnemonic(&secp, &root, word_count, index),
+            }
+        }
+    }).buffered(parallelism)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_mnemonic_stream() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+        let expected: Vec<_> = (0..20).map(|index| to_mnemonic(&secp, &root, 12, index)).collect();
+
+        // without runtime
+        let stream = mnemonic_stream(&secp, &root, 12, 0..20);
+        let derived: Vec<_> = futures::executor::block_on(stream.collect());
+        assert_eq!(derived, expected);
+
+        // in tokio runtime
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let stream = mnemonic_stream(&secp, &root, 12, 0..20);
+        let derived: Vec<_> = runtime.block_on(stream.collect());
+        assert_eq!(derived, expected);
+
+        let stream = mnemonic_stream(&secp, &root, 12, vec![0, 0x80000000, 1]);
+        let derived: Vec<_> = runtime.block_on(stream.collect());
+        assert_eq!(derived[1], Err(Error::InvalidIndex(0x80000000)));
+        assert_eq!(derived[2], to_mnemonic(&secp, &root, 12, 1));
+        let stream = mnemonic_stream(&secp, &root, 13, 0..1);
+        let derived: Vec<_> = runtime.block_on(stream.collect());
+        assert_eq!(derived, vec![Err(Error::InvalidWordCount(13))]);
+    }
+}
@@ -0,0 +1,55 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Deterministic UUIDs from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use ::uuid::{Builder, Uuid};
+
+use super::{app_index, derive_app_path, Error};
+
+/// Derive UUID from the root key
+///
+/// This is not a standard application, derivation path is `m/83696968'/85857368'/{index}'`,
+/// 85857368 is `UUID`. UUID is built from the first 16 bytes of the derived entropy,
+/// version and variant bits are then forced to look like a random UUID version 4,
+/// so only 122 bits of the UUID come from the entropy.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_uuid<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<Uuid, Error> {
+    let entropy = derive_app_path(secp, root, &[app_index::UUID, index])?;
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&entropy[..16]);
+    Ok(Builder::from_random_bytes(bytes).into_uuid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use ::uuid::{Variant, Version};
+    use crate::vectors;
+
+    #[test]
+    fn test_uuid() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let uuid = to_uuid(&secp, &root, 0).unwrap();
+        assert_eq!(uuid.to_string(), "634d9868-2ae5-4415-95ca-64607e4aff34");
+        assert_eq!(uuid, to_uuid(&secp, &root, 0).unwrap());
+        assert_ne!(uuid, to_uuid(&secp, &root, 1).unwrap());
+        assert_eq!(uuid.get_version(), Some(Version::Random));
+        assert_eq!(uuid.get_variant(), Variant::RFC4122);
+
+        let index = 0x80000000;
+        assert_eq!(to_uuid(&secp, &root, index), Err(Error::InvalidIndex(index)));
+    }
+}
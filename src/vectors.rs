@@ -0,0 +1,88 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki
+//!
+//! Shared by `self_test` and unit tests.
+
+/// Master key used in all test vectors
+pub const ROOT: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaL\
+                        LHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+/// Raw entropy: (derivation path, entropy)
+pub const RAW: [(&str, [u8; 64]); 2] = [
+    ("m/0'/0'", [0xef, 0xec, 0xfb, 0xcc, 0xff, 0xea, 0x31, 0x32,
+                 0x14, 0x23, 0x2d, 0x29, 0xe7, 0x15, 0x63, 0xd9,
+                 0x41, 0x22, 0x9a, 0xfb, 0x43, 0x38, 0xc2, 0x1f,
+                 0x95, 0x17, 0xc4, 0x1a, 0xaa, 0x0d, 0x16, 0xf0,
+                 0x0b, 0x83, 0xd2, 0xa0, 0x9e, 0xf7, 0x47, 0xe7,
+                 0xa6, 0x4e, 0x8e, 0x2b, 0xd5, 0xa1, 0x48, 0x69,
+                 0xe6, 0x93, 0xda, 0x66, 0xce, 0x94, 0xac, 0x2d,
+                 0xa5, 0x70, 0xab, 0x7e, 0xe4, 0x86, 0x18, 0xf7,
+    ]),
+    ("m/0'/1'", [0x70, 0xc6, 0xe3, 0xe8, 0xeb, 0xee, 0x8d, 0xc4,
+                 0xc0, 0xdb, 0xba, 0x66, 0x07, 0x68, 0x19, 0xbb,
+                 0x8c, 0x09, 0x67, 0x25, 0x27, 0xc4, 0x27, 0x7c,
+                 0xa8, 0x72, 0x95, 0x32, 0xad, 0x71, 0x18, 0x72,
+                 0x21, 0x8f, 0x82, 0x69, 0x19, 0xf6, 0xb6, 0x72,
+                 0x18, 0xad, 0xde, 0x99, 0x01, 0x8a, 0x6d, 0xf9,
+                 0x09, 0x5a, 0xb2, 0xb5, 0x8d, 0x80, 0x3b, 0x5b,
+                 0x93, 0xec, 0x98, 0x02, 0x08, 0x5a, 0x69, 0x0e,
+    ]),
+];
+
+/// HD-Seed WIF: (index, private key)
+pub const WIF: (u32, &str) = (0, "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp");
+
+/// XPRV: (index, extended private key)
+pub const XPRV: (u32, &str) = (
+    0,
+    "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UG\
+     XMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX",
+);
+
+/// HEX: (number of bytes, index, entropy)
+pub const HEX: (u32, u32, [u8; 64]) = (64, 0, [
+    0x49, 0x2d, 0xb4, 0x69, 0x8c, 0xf3, 0xb7, 0x3a,
+    0x5a, 0x24, 0x99, 0x8a, 0xa3, 0xe9, 0xd7, 0xfa,
+    0x96, 0x27, 0x5d, 0x85, 0x72, 0x4a, 0x91, 0xe7,
+    0x1a, 0xa2, 0xd6, 0x45, 0x44, 0x2f, 0x87, 0x85,
+    0x55, 0xd0, 0x78, 0xfd, 0x1f, 0x1f, 0x67, 0xe3,
+    0x68, 0x97, 0x6f, 0x04, 0x13, 0x7b, 0x1f, 0x7a,
+    0x0d, 0x19, 0x23, 0x21, 0x36, 0xca, 0x50, 0xc4,
+    0x46, 0x14, 0xaf, 0x72, 0xb5, 0x58, 0x2a, 0x5c,
+]);
+
+/// BIP85-DRNG: first 80 bytes of output seeded with entropy derived at `m/0'/0'` (`RAW[0]`)
+pub const DRNG: [u8; 80] = [
+    0xb7, 0x8b, 0x1e, 0xe6, 0xb3, 0x45, 0xea, 0xe6,
+    0x83, 0x6c, 0x2d, 0x53, 0xd3, 0x3c, 0x64, 0xcd,
+    0xaf, 0x9a, 0x69, 0x64, 0x87, 0xbe, 0x81, 0xb0,
+    0x3e, 0x82, 0x2d, 0xc8, 0x4b, 0x3f, 0x1c, 0xd8,
+    0x83, 0xd7, 0x55, 0x9e, 0x53, 0xd1, 0x75, 0xf2,
+    0x43, 0xe4, 0xc3, 0x49, 0xe8, 0x22, 0xa9, 0x57,
+    0xbb, 0xff, 0x92, 0x24, 0xbc, 0x5d, 0xde, 0x94,
+    0x92, 0xef, 0x54, 0xe8, 0xa4, 0x39, 0xf6, 0xbc,
+    0x8c, 0x73, 0x55, 0xb8, 0x7a, 0x92, 0x5a, 0x37,
+    0xee, 0x40, 0x5a, 0x75, 0x02, 0x99, 0x11, 0x11,
+];
+
+/// DICE: (number of sides, number of rolls, index, rolls)
+pub const DICE: (u16, u32, u32, [u16; 10]) = (6, 10, 0, [1, 0, 0, 2, 0, 1, 5, 5, 2, 4]);
+
+/// BIP39 english mnemonics: (number of words, index, mnemonic)
+#[cfg(feature = "mnemonic")]
+pub const MNEMONIC: [(u32, u32, &str); 3] = [
+    (12, 0, "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"),
+    (18, 0, "near account window bike charge season chef number sketch tomorrow excuse sniff \
+             circle vital hockey outdoor supply token"),
+    (24, 0, "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget \
+             divorce twin tonight reason outdoor destroy simple truth cigar social volcano"),
+];
+
+/// PWD BASE64: (password length, index, password)
+pub const PWD_BASE64: (u32, u32, &str) = (21, 0, "dKLoepugzdVJvdL56ogNV");
+
+/// PWD BASE85: (password length, index, password)
+pub const PWD_BASE85: (u32, u32, &str) = (12, 0, "_s`{TW89)i4`");
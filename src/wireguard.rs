@@ -0,0 +1,120 @@
+// Rust implementation of bip-85
+// Written in 2020 by
+//     Rita Kitic <rikitau@protonmail.com>
+
+//! WireGuard keys from the derived entropy
+
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::ExtendedPrivKey;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use super::{app_index, base64_encode, derive_app_path, Error};
+
+/// Derive WireGuard keypair from the root key
+///
+/// Returns base64 encoded private and public keys, as `wg genkey` and `wg pubkey` output them.
+/// The keys are the same as `to_x25519_keypair` for the same index.
+///
+/// This is not a standard application, derivation path is `m/83696968'/8771'/{index}'`,
+/// 8771 is `WG`.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_wireguard_keypair<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<(String, String), Error> {
+    let (private, public) = to_x25519_keypair(secp, root, index)?;
+    Ok((base64_encode(&private), base64_encode(&public)))
+}
+
+/// Derive raw X25519 keypair from the root key
+///
+/// Returns clamped private key and public key bytes, they can be used as
+/// `x25519_dalek::StaticSecret` and `x25519_dalek::PublicKey` for ECDH.
+///
+/// The private key is the first 32 bytes of the entropy derived at
+/// `m/83696968'/8771'/{index}'`, clamped as Curve25519 private key: 3 lowest bits are
+/// cleared, the highest bit is cleared and the second highest is set.
+///
+/// This is exactly the WireGuard key of `to_wireguard_keypair` at the same index in raw
+/// form, not a separate application. Don't use the keypair for anything else than
+/// the WireGuard tunnel of that index.
+///
+/// `index` can be any number lower than `0x80000000`
+pub fn to_x25519_keypair<C: secp256k1::Signing>(
+        secp: &Secp256k1<C>,
+        root: &ExtendedPrivKey,
+        index: u32,
+    ) -> Result<([u8; 32], [u8; 32]), Error> {
+    let entropy = derive_app_path(secp, root, &[app_index::WIREGUARD, index])?;
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&entropy[..32]);
+    Ok(keypair(key))
+}
+
+/// Clamp the private key and compute its public key
+fn keypair(mut key: [u8; 32]) -> ([u8; 32], [u8; 32]) {
+    key[0] &= 248;
+    key[31] &= 127;
+    key[31] |= 64;
+    let public = PublicKey::from(&StaticSecret::from(key));
+    (key, public.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use crate::vectors;
+
+    #[test]
+    fn test_keypair() {
+        // Alice's key from RFC 7748, section 6.1
+        let key = [0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d,
+                   0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2, 0x66, 0x45,
+                   0xdf, 0x4c, 0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a,
+                   0xb1, 0x77, 0xfb, 0xa5, 0x1d, 0xb9, 0x2c, 0x2a,
+        ];
+        let (private, public) = keypair(key);
+        assert_eq!(base64_encode(&private), "cAdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LGo=");
+        assert_eq!(base64_encode(&public), "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo=");
+    }
+
+    #[test]
+    fn test_wireguard_keypair() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let (private, public) = to_wireguard_keypair(&secp, &root, 0).unwrap();
+        assert_eq!(private, "mH/zOm/yTXklhlXBcz7Ouom3XLQp1wSvGynurST3N30=");
+        assert_eq!(public, "rz2X948GeDnIPBFGMOJar5xFb+xN8w1KUlcHvW3Q4jQ=");
+        let (raw_private, raw_public) = to_x25519_keypair(&secp, &root, 0).unwrap();
+        assert_eq!((private.clone(), public), (base64_encode(&raw_private), base64_encode(&raw_public)));
+        assert_eq!(private.len(), 44);
+        assert_ne!(private, to_wireguard_keypair(&secp, &root, 1).unwrap().0);
+    }
+
+    #[test]
+    fn test_x25519_dh() {
+        let root = ExtendedPrivKey::from_str(vectors::ROOT).unwrap();
+        let secp = Secp256k1::new();
+
+        let (alice_private, alice_public) = to_x25519_keypair(&secp, &root, 0).unwrap();
+        let (bob_private, bob_public) = to_x25519_keypair(&secp, &root, 1).unwrap();
+        assert_eq!(alice_private[0] & 7, 0);
+        assert_eq!(alice_private[31] & 0xc0, 0x40);
+        assert_ne!(alice_public, bob_public);
+
+        let alice = StaticSecret::from(alice_private);
+        let bob = StaticSecret::from(bob_private);
+        let alice_shared = alice.diffie_hellman(&PublicKey::from(bob_public));
+        let bob_shared = bob.diffie_hellman(&PublicKey::from(alice_public));
+        assert_eq!(alice_shared.as_bytes(), bob_shared.as_bytes());
+
+        let (private, public) = to_wireguard_keypair(&secp, &root, 0).unwrap();
+        assert_eq!(private, base64_encode(&alice_private));
+        assert_eq!(public, base64_encode(&alice_public));
+    }
+}